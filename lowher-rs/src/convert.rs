@@ -0,0 +1,148 @@
+//! Case-conversion engine: segments identifiers/words into their constituent parts and
+//! reassembles them in a target case style (snake_case, kebab-case, camelCase, PascalCase,
+//! Title Case).
+
+use regex::Regex;
+
+/// A target case style for [`convert_text`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseStyle {
+    Snake,
+    Kebab,
+    Camel,
+    Pascal,
+    Title,
+}
+
+impl CaseStyle {
+    /// Parses a `--case` flag value, accepting a few common spellings per style.
+    pub fn parse(value: &str) -> Option<CaseStyle> {
+        match value.to_lowercase().as_str() {
+            "snake" | "snake_case" => Some(CaseStyle::Snake),
+            "kebab" | "kebab-case" => Some(CaseStyle::Kebab),
+            "camel" | "camelcase" => Some(CaseStyle::Camel),
+            "pascal" | "pascalcase" => Some(CaseStyle::Pascal),
+            "title" | "titlecase" | "title_case" => Some(CaseStyle::Title),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a token into its constituent words on explicit delimiters (`_`, `-`, space),
+/// lowercase-to-uppercase transitions (`myWord` -> `my`, `Word`), acronym boundaries
+/// (`HTTPResponse` -> `HTTP`, `Response`), and digit/letter transitions.
+fn segment_words(token: &str) -> Vec<String> {
+    let chars: Vec<char> = token.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if let Some(&prev) = chars.get(i.wrapping_sub(1)).filter(|_| i > 0) {
+            let is_lower_to_upper = prev.is_lowercase() && c.is_uppercase();
+            let is_acronym_boundary = prev.is_uppercase()
+                && c.is_uppercase()
+                && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let is_digit_transition =
+                prev.is_ascii_digit() != c.is_ascii_digit()
+                    && prev.is_alphanumeric()
+                    && c.is_alphanumeric();
+
+            if (is_lower_to_upper || is_acronym_boundary || is_digit_transition)
+                && !current.is_empty()
+            {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+        None => String::new(),
+    }
+}
+
+fn apply_case(words: &[String], style: CaseStyle) -> String {
+    match style {
+        CaseStyle::Snake => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        CaseStyle::Kebab => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("-"),
+        CaseStyle::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.to_lowercase() } else { capitalize(w) })
+            .collect::<Vec<_>>()
+            .join(""),
+        CaseStyle::Pascal => words.iter().map(|w| capitalize(w)).collect::<Vec<_>>().join(""),
+        CaseStyle::Title => words.iter().map(|w| capitalize(w)).collect::<Vec<_>>().join(" "),
+    }
+}
+
+/// Reshapes a single identifier/word into the given case style.
+///
+/// A token made up entirely of delimiters (`-`, `_`) segments to no words at all, so it's
+/// returned unchanged rather than silently vanishing. A token with leading or trailing
+/// delimiters around real words (e.g. `foo-`) keeps those outer delimiters verbatim:
+/// `segment_words` drops every delimiter, the inner ones are replaced by the style's joiner,
+/// and the outer ones are copied back from the original token.
+pub fn convert_token(token: &str, style: CaseStyle) -> String {
+    let words = segment_words(token);
+    if words.is_empty() {
+        return token.to_string();
+    }
+
+    let is_delimiter = |c: char| c == '_' || c == '-';
+    let leading_end = token.find(|c| !is_delimiter(c)).unwrap_or(0);
+    let trailing_start = token
+        .rfind(|c| !is_delimiter(c))
+        .map(|i| i + 1)
+        .unwrap_or(token.len());
+
+    format!(
+        "{}{}{}",
+        &token[..leading_end],
+        apply_case(&words, style),
+        &token[trailing_start..]
+    )
+}
+
+/// Reshapes every identifier-like token in `text` into the given case style, leaving
+/// surrounding punctuation and whitespace untouched.
+pub fn convert_text(text: &str, style: CaseStyle) -> String {
+    let token_pattern = Regex::new(r"[A-Za-z0-9_-]+(?:['’][A-Za-z0-9_-]+)*").unwrap();
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+
+    for m in token_pattern.find_iter(text) {
+        result.push_str(&text[last_end..m.start()]);
+        result.push_str(&convert_token(m.as_str(), style));
+        last_end = m.end();
+    }
+
+    result.push_str(&text[last_end..]);
+    result
+}
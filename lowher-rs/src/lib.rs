@@ -0,0 +1,425 @@
+//! # Lowher
+//!
+//! Lowher converts text to lowercase while optionally preserving the case of proper
+//! nouns, acronyms, and protected spans (code, URLs, emails, paths, mentions, hashtags).
+//! It also supports reshaping text into title case or a target identifier case style.
+//!
+//! This crate exposes [`Config`]/[`ConfigBuilder`] and [`transform`] so the casing logic
+//! can be embedded in other Rust programs without shelling out to the `lowher` binary.
+
+pub mod convert;
+pub mod protect;
+
+use convert::CaseStyle;
+use protect::{mark_protected_spans, unmark_protected_spans, ProtectOptions};
+use regex::Regex;
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+
+/// Small words (articles, coordinating conjunctions, short prepositions) that stay
+/// lowercase in title case mode unless they open or close the line.
+const TITLE_CASE_STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "and", "but", "or", "nor", "for", "of", "to", "in", "on", "at", "by",
+    "with", "as", "if", "vs",
+];
+
+/// Configuration for a single [`transform`] call.
+///
+/// Build one with [`Config::builder`], or use [`Config::default`] for the classic
+/// behavior (preserve capitalized words, lowercase the first letter of each sentence,
+/// protect code/URLs/emails/paths/mentions/hashtags).
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub preserve_capitalized: bool,
+    pub preserve_sentence_case: bool,
+    pub title_case: bool,
+    pub case: Option<CaseStyle>,
+    pub ignore_words: HashSet<String>,
+    pub protect: ProtectOptions,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            preserve_capitalized: true,
+            preserve_sentence_case: false,
+            title_case: false,
+            case: None,
+            ignore_words: HashSet::new(),
+            protect: ProtectOptions::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Starts a [`ConfigBuilder`] seeded with the default configuration.
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+}
+
+/// Builder for [`Config`]. Each setter takes `self` by value so calls can be chained.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn preserve_capitalized(mut self, value: bool) -> Self {
+        self.config.preserve_capitalized = value;
+        self
+    }
+
+    pub fn preserve_sentence_case(mut self, value: bool) -> Self {
+        self.config.preserve_sentence_case = value;
+        self
+    }
+
+    pub fn title_case(mut self, value: bool) -> Self {
+        self.config.title_case = value;
+        self
+    }
+
+    pub fn case(mut self, style: CaseStyle) -> Self {
+        self.config.case = Some(style);
+        self
+    }
+
+    pub fn ignore_word(mut self, word: impl Into<String>) -> Self {
+        self.config.ignore_words.insert(word.into());
+        self
+    }
+
+    pub fn ignore_words(mut self, words: impl IntoIterator<Item = String>) -> Self {
+        self.config.ignore_words.extend(words);
+        self
+    }
+
+    pub fn protect(mut self, protect: ProtectOptions) -> Self {
+        self.config.protect = protect;
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.config
+    }
+}
+
+fn process_text(
+    text: &str,
+    preserve_capitalized: bool,
+    preserve_sentence_case: bool,
+    ignore_words: &HashSet<String>,
+) -> String {
+    let sentence_pattern = Regex::new(r"(?:^|[.!?]\s+)([A-Z][^.!?]*(?:[.!?]|$))").unwrap();
+    let word_pattern = Regex::new(r"\b\w+\b").unwrap();
+    let mut result = String::with_capacity(text.len());
+    let mut last_end = 0;
+
+    for sentence_cap in sentence_pattern.captures_iter(text) {
+        let sentence = sentence_cap.get(1).unwrap();
+        let sentence_start = sentence.start();
+        let sentence_end = sentence.end();
+
+        // Add any text before the sentence
+        result.push_str(&text[last_end..sentence_start]);
+
+        let mut sentence_result = String::with_capacity(sentence.len());
+        let mut sentence_last_end = 0;
+        let mut first_word_is_ignored = false;
+
+        for word_cap in word_pattern.captures_iter(sentence.as_str()) {
+            let word = word_cap.get(0).unwrap();
+            let word_start = word.start();
+            let word_end = word.end();
+
+            // Add any text between the last word and this one
+            sentence_result.push_str(&sentence.as_str()[sentence_last_end..word_start]);
+
+            let word_str = word.as_str();
+            let is_first_word = word_start == 0;
+            let ignored_word = ignore_words.iter().find(|w| w.eq_ignore_ascii_case(word_str));
+
+            if let Some(canonical) = ignored_word {
+                sentence_result.push_str(canonical);
+                first_word_is_ignored |= is_first_word;
+            } else if word_str.chars().all(char::is_uppercase)
+                || (preserve_capitalized
+                    && word_str.chars().next().unwrap().is_uppercase()
+                    && !is_first_word)
+                || (preserve_sentence_case && is_first_word)
+            {
+                sentence_result.push_str(word_str);
+            } else {
+                sentence_result.push_str(&word_str.to_lowercase());
+            }
+
+            sentence_last_end = word_end;
+        }
+
+        // Add any remaining text in the sentence
+        sentence_result.push_str(&sentence.as_str()[sentence_last_end..]);
+
+        // If not preserving sentence case, lowercase the first character, unless
+        // the first word's casing is pinned by the ignore list
+        if !preserve_sentence_case && !first_word_is_ignored {
+            if let Some(first_char) = sentence_result.chars().next() {
+                let lowercased = first_char.to_lowercase().collect::<String>();
+                sentence_result.replace_range(0..1, &lowercased);
+            }
+        }
+
+        result.push_str(&sentence_result);
+        last_end = sentence_end;
+    }
+
+    // Add any remaining text
+    result.push_str(&text[last_end..]);
+    result
+}
+
+fn title_case_line(line: &str) -> String {
+    let word_pattern = Regex::new(r"[A-Za-z]+(?:['’][A-Za-z]+)*").unwrap();
+    let words: Vec<_> = word_pattern.find_iter(line).collect();
+    let last_word_index = words.len().saturating_sub(1);
+
+    let mut result = String::with_capacity(line.len());
+    let mut last_end = 0;
+
+    for (index, word) in words.iter().enumerate() {
+        result.push_str(&line[last_end..word.start()]);
+
+        let word_str = word.as_str();
+        let is_first_word = index == 0;
+        let is_last_word = index == last_word_index;
+        let is_stop_word = TITLE_CASE_STOP_WORDS.contains(&word_str.to_lowercase().as_str());
+
+        if is_stop_word && !is_first_word && !is_last_word {
+            result.push_str(&word_str.to_lowercase());
+        } else {
+            let mut chars = word_str.chars();
+            if let Some(first_char) = chars.next() {
+                result.extend(first_char.to_uppercase());
+                result.push_str(&chars.as_str().to_lowercase());
+            }
+        }
+
+        last_end = word.end();
+    }
+
+    result.push_str(&line[last_end..]);
+    result
+}
+
+fn title_case_text(text: &str) -> String {
+    text.split('\n')
+        .map(title_case_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Applies `config` to `text`, returning the transformed text.
+///
+/// Protected spans (code, and whichever of URLs/emails/paths/mentions/hashtags
+/// `config.protect` enables) are swapped out before transformation and restored
+/// afterward, so they always pass through unchanged.
+pub fn transform(config: &Config, text: &str) -> String {
+    let (marked_text, placeholders, spans) = mark_protected_spans(text, &config.protect);
+
+    let processed_text = if let Some(style) = config.case {
+        convert::convert_text(&marked_text, style)
+    } else if config.title_case {
+        title_case_text(&marked_text)
+    } else {
+        process_text(
+            &marked_text,
+            config.preserve_capitalized,
+            config.preserve_sentence_case,
+            &config.ignore_words,
+        )
+    };
+
+    unmark_protected_spans(&processed_text, &placeholders, &spans)
+}
+
+/// Streams `reader` through `transform` line by line, writing results to `writer` as soon
+/// as each chunk is complete, instead of buffering the whole input in memory.
+///
+/// A chunk is one or more lines: a fenced code block may span several lines, so lines are
+/// accumulated until any open fence closes before being transformed and flushed, keeping
+/// peak memory bounded by the largest chunk rather than the whole input.
+///
+/// Chunk boundaries are fence boundaries, not sentence boundaries, so a sentence split
+/// across a chunk boundary (e.g. across a line break outside any fence) is sentence-cased
+/// as if it were two separate sentences instead of one. [`transform`] on the whole input at
+/// once does not have this limitation, so streamed and non-streamed output can diverge on
+/// sentence casing for such input.
+pub fn transform_stream<R: BufRead, W: Write>(
+    config: &Config,
+    mut reader: R,
+    mut writer: W,
+) -> io::Result<()> {
+    let mut pending = String::new();
+    let mut in_fence = false;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+
+        if line.matches("```").count() % 2 == 1 {
+            in_fence = !in_fence;
+        }
+        pending.push_str(&line);
+
+        if !in_fence {
+            writer.write_all(transform(config, &pending).as_bytes())?;
+            pending.clear();
+        }
+    }
+
+    if !pending.is_empty() {
+        writer.write_all(transform(config, &pending).as_bytes())?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "This is a TEST String with ACRONYMS like NASA and proper Nouns like John Doe. \
+                           Here's some `inlineCode` and a code block:
+                           ```
+                           function testFunction() {
+                               console.log('HELLO WORLD');
+                           }
+                           ```
+                           More TEXT here. Let's include an email: John.Doe@Example.com and a URL: https://www.Example.com. \
+                           Another sentence. And one more.";
+
+    #[test]
+    fn preserves_capitalized_words_by_default() {
+        let config = Config::default();
+        let result = transform(&config, SAMPLE);
+        assert!(result.starts_with("this is a TEST String with ACRONYMS like NASA and proper Nouns like John Doe."));
+        assert!(result.contains("`inlineCode`"));
+    }
+
+    #[test]
+    fn lowercase_all_strips_proper_noun_capitalization() {
+        let config = Config::builder().preserve_capitalized(false).build();
+        let result = transform(&config, SAMPLE);
+        assert!(result.starts_with("this is a TEST string with ACRONYMS like NASA and proper nouns like john doe."));
+    }
+
+    #[test]
+    fn preserve_sentence_case_keeps_first_letter() {
+        let config = Config::builder().preserve_sentence_case(true).build();
+        let result = transform(&config, SAMPLE);
+        assert!(result.starts_with("This is a TEST String"));
+    }
+
+    #[test]
+    fn protected_spans_survive_transformation() {
+        let config = Config::default();
+        let result = transform(
+            &config,
+            "Contact John.Doe@Example.com or visit https://Example.com/Docs.",
+        );
+        assert!(result.contains("John.Doe@Example.com"));
+        assert!(result.contains("https://Example.com/Docs"));
+    }
+
+    #[test]
+    fn protected_spans_survive_word_rewriting_transforms() {
+        let input = "Contact John.Doe@Example.com or visit https://Example.com/Docs.";
+
+        let title_config = Config::builder().title_case(true).build();
+        let title_result = transform(&title_config, input);
+        assert!(title_result.contains("John.Doe@Example.com"));
+        assert!(title_result.contains("https://Example.com/Docs"));
+
+        let case_config = Config::builder().case(CaseStyle::Snake).build();
+        let case_result = transform(&case_config, input);
+        assert!(case_result.contains("John.Doe@Example.com"));
+        assert!(case_result.contains("https://Example.com/Docs"));
+
+        let lowercase_all_config = Config::builder().preserve_capitalized(false).build();
+        let lowercase_all_result = transform(&lowercase_all_config, input);
+        assert!(lowercase_all_result.contains("John.Doe@Example.com"));
+        assert!(lowercase_all_result.contains("https://Example.com/Docs"));
+    }
+
+    #[test]
+    fn title_case_keeps_stop_words_lowercase_except_first_and_last() {
+        let config = Config::builder().title_case(true).build();
+        let result = transform(&config, "the quick brown fox of the forest");
+        assert_eq!(result, "The Quick Brown Fox of the Forest");
+    }
+
+    #[test]
+    fn title_case_capitalizes_first_and_last_word_despite_leading_punctuation() {
+        let config = Config::builder().title_case(true).build();
+        assert_eq!(transform(&config, "  the fox"), "  The Fox");
+        assert_eq!(
+            transform(&config, "\"the fox jumps\""),
+            "\"The Fox Jumps\""
+        );
+    }
+
+    #[test]
+    fn title_case_keeps_apostrophes_inside_words() {
+        let config = Config::builder().title_case(true).build();
+        assert_eq!(transform(&config, "don't forget it's john's"), "Don't Forget It's John's");
+    }
+
+    #[test]
+    fn ignore_words_pin_canonical_spelling() {
+        let config = Config::builder()
+            .ignore_word("iPhone")
+            .ignore_word("NASA")
+            .build();
+        let result = transform(&config, "The IPHONE and nasa both stay as written.");
+        assert_eq!(result, "the iPhone and NASA both stay as written.");
+    }
+
+    #[test]
+    fn case_conversion_reshapes_identifiers() {
+        let config = Config::builder().case(CaseStyle::Snake).build();
+        let result = transform(&config, "HTTPResponse_myWord-example2");
+        assert_eq!(result, "http_response_my_word_example_2");
+    }
+
+    #[test]
+    fn case_conversion_keeps_delimiter_only_tokens_instead_of_deleting_them() {
+        let config = Config::builder().case(CaseStyle::Snake).build();
+        assert_eq!(transform(&config, "foo - bar"), "foo - bar");
+        assert_eq!(transform(&config, "wait -- really"), "wait -- really");
+        assert_eq!(transform(&config, "foo-"), "foo-");
+    }
+
+    #[test]
+    fn case_conversion_title_style_keeps_apostrophes_inside_words() {
+        let config = Config::builder().case(CaseStyle::Title).build();
+        assert_eq!(transform(&config, "it's"), "It's");
+    }
+
+    #[test]
+    fn transform_stream_processes_line_by_line_and_protects_multiline_fences() {
+        let config = Config::default();
+        let input = "This is Line One.\n```\nKeepTHIS as-is\n```\nAnd Line Two.\n";
+
+        let mut output = Vec::new();
+        transform_stream(&config, input.as_bytes(), &mut output).unwrap();
+
+        let result = String::from_utf8(output).unwrap();
+        assert_eq!(
+            result,
+            "this is Line One.\n```\nKeepTHIS as-is\n```\nand Line Two.\n"
+        );
+    }
+}
@@ -1,166 +1,82 @@
-//! # Lowher
-//!
-//! Lowher is a command-line tool that converts text to lowercase while optionally
-//! preserving the case of proper nouns, acronyms, and code blocks.
-//!
-//! ## Installation
-//!
-//! 1. Ensure you have Rust and Cargo installed. If not, visit https://www.rust-lang.org/tools/install
-//!
-//! 2. Clone this repository:
-//!    ```
-//!    git clone https://github.com/yourusername/lowher.git
-//!    cd lowher
-//!    ```
-//!
-//! 3. Build the project:
-//!    ```
-//!    cargo build --release
-//!    ```
-//!
-//! 4. The binary will be available at `target/release/lowher`
-//!
-//! ## Usage
-//!
-//! Run the program with the input file and optional flags:
-//!
-//! ```
-//! ./lowher [OPTIONS] <filename>
-//! ```
-//!
-//! Options:
-//!   -a, --lowercase-all    Lowercase all words, including those starting with capital letters
-//!
-//! The processed text will be printed to stdout. To save the output to a file, use:
-//!
-//! ```
-//! ./lowher [OPTIONS] input.txt > output.txt
-//! ```
-//!
-//! ## Features
-//!
-//! - Converts text to lowercase
-//! - Preserves case of words that are all uppercase (assumed to be acronyms)
-//! - Optionally preserves case of words that start with an uppercase letter (assumed to be proper nouns)
-//! - Preserves text within code blocks (text between ``` or single backticks)
+//! Command-line front-end for the `lowher` library: parses flags into a [`lowher::Config`]
+//! and runs [`lowher::transform`] over a file or stdin.
 
-use regex::Regex;
+use lowher::convert::CaseStyle;
+use lowher::protect::ProtectOptions;
+use lowher::Config;
 use std::env;
 use std::fs;
-use std::io::{self, Read};
-
-fn mark_code_blocks(text: &str) -> (String, Vec<String>, Vec<String>) {
-    let code_block_pattern = Regex::new(r"(```[\s\S]*?```|`[^`]*`)").unwrap();
-    let code_blocks: Vec<String> = code_block_pattern
-        .find_iter(text)
-        .map(|m| m.as_str().to_string())
-        .collect();
-    let placeholders: Vec<String> = (0..code_blocks.len())
-        .map(|i| format!("__CODE_BLOCK_{i}__"))
-        .collect();
-
-    let mut marked_text = text.to_string();
-    for (placeholder, code_block) in placeholders.iter().zip(code_blocks.iter()) {
-        marked_text = marked_text.replace(code_block, placeholder);
-    }
-
-    (marked_text, placeholders, code_blocks)
-}
-
-fn unmark_code_blocks(text: &str, placeholders: &[String], code_blocks: &[String]) -> String {
-    let mut unmarked_text = text.to_string();
-    for (placeholder, code_block) in placeholders.iter().zip(code_blocks.iter()) {
-        unmarked_text = unmarked_text.replace(placeholder, code_block);
-    }
-    unmarked_text
-}
-
-fn process_text(text: &str, preserve_capitalized: bool, preserve_sentence_case: bool) -> String {
-    let sentence_pattern = Regex::new(r"(?:^|[.!?]\s+)([A-Z][^.!?]*(?:[.!?]|$))").unwrap();
-    let word_pattern = Regex::new(r"\b\w+\b").unwrap();
-    let mut result = String::with_capacity(text.len());
-    let mut last_end = 0;
-
-    for sentence_cap in sentence_pattern.captures_iter(text) {
-        let sentence = sentence_cap.get(1).unwrap();
-        let sentence_start = sentence.start();
-        let sentence_end = sentence.end();
-
-        // Add any text before the sentence
-        result.push_str(&text[last_end..sentence_start]);
-
-        let mut sentence_result = String::with_capacity(sentence.len());
-        let mut sentence_last_end = 0;
-
-        for word_cap in word_pattern.captures_iter(sentence.as_str()) {
-            let word = word_cap.get(0).unwrap();
-            let word_start = word.start();
-            let word_end = word.end();
-
-            // Add any text between the last word and this one
-            sentence_result.push_str(&sentence.as_str()[sentence_last_end..word_start]);
-
-            let word_str = word.as_str();
-            let is_first_word = word_start == 0;
-
-            if word_str.chars().all(char::is_uppercase)
-                || (preserve_capitalized
-                    && word_str.chars().next().unwrap().is_uppercase()
-                    && !is_first_word)
-                || (preserve_sentence_case && is_first_word)
-            {
-                sentence_result.push_str(word_str);
-            } else {
-                sentence_result.push_str(&word_str.to_lowercase());
-            }
-
-            sentence_last_end = word_end;
-        }
-
-        // Add any remaining text in the sentence
-        sentence_result.push_str(&sentence.as_str()[sentence_last_end..]);
-
-        // If not preserving sentence case, lowercase the first character
-        if !preserve_sentence_case {
-            if let Some(first_char) = sentence_result.chars().next() {
-                let lowercased = first_char.to_lowercase().collect::<String>();
-                sentence_result.replace_range(0..1, &lowercased);
-            }
-        }
-
-        result.push_str(&sentence_result);
-        last_end = sentence_end;
-    }
-
-    // Add any remaining text
-    result.push_str(&text[last_end..]);
-    result
-}
-
-fn lowher(text: &str, preserve_capitalized: bool, preserve_sentence_case: bool) -> String {
-    let (marked_text, placeholders, code_blocks) = mark_code_blocks(text);
-    let processed_text = process_text(&marked_text, preserve_capitalized, preserve_sentence_case);
-    unmark_code_blocks(&processed_text, &placeholders, &code_blocks)
-}
+use std::io::{self, BufReader, BufWriter, Read};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
-    let mut preserve_capitalized = true;
-    let mut preserve_sentence_case = false;
+    let mut builder = Config::builder();
+    let mut protect_options = ProtectOptions::default();
+    let mut stream = false;
     let mut input_source = None;
 
-    for arg in args.iter().skip(1) {
+    let mut args_iter = args.iter().skip(1);
+    while let Some(arg) = args_iter.next() {
         match arg.as_str() {
-            "-a" | "--lowercase-all" => preserve_capitalized = false,
-            "-s" | "--preserve-sentence-case" => preserve_sentence_case = true,
+            "-a" | "--lowercase-all" => builder = builder.preserve_capitalized(false),
+            "-s" | "--preserve-sentence-case" => builder = builder.preserve_sentence_case(true),
+            "-t" | "--title-case" => builder = builder.title_case(true),
+            "--stream" => stream = true,
+            "--ignore" => match args_iter.next() {
+                Some(word) => builder = builder.ignore_word(word.clone()),
+                None => {
+                    eprintln!("--ignore requires a word");
+                    std::process::exit(1);
+                }
+            },
+            "--ignore-file" => match args_iter.next() {
+                Some(path) => {
+                    let contents = fs::read_to_string(path)?;
+                    builder = builder.ignore_words(
+                        contents
+                            .lines()
+                            .map(str::trim)
+                            .filter(|word| !word.is_empty())
+                            .map(str::to_string),
+                    );
+                }
+                None => {
+                    eprintln!("--ignore-file requires a path");
+                    std::process::exit(1);
+                }
+            },
+            "--case" => match args_iter.next().and_then(|value| CaseStyle::parse(value)) {
+                Some(style) => builder = builder.case(style),
+                None => {
+                    eprintln!("--case requires one of: snake, kebab, camel, pascal, title");
+                    std::process::exit(1);
+                }
+            },
+            "--preserve-urls" => match args_iter.next().and_then(|v| parse_bool(v)) {
+                Some(value) => protect_options.preserve_urls = value,
+                None => {
+                    eprintln!("--preserve-urls requires true or false");
+                    std::process::exit(1);
+                }
+            },
+            "--preserve-emails" => match args_iter.next().and_then(|v| parse_bool(v)) {
+                Some(value) => protect_options.preserve_emails = value,
+                None => {
+                    eprintln!("--preserve-emails requires true or false");
+                    std::process::exit(1);
+                }
+            },
+            "--preserve-paths" => match args_iter.next().and_then(|v| parse_bool(v)) {
+                Some(value) => protect_options.preserve_paths = value,
+                None => {
+                    eprintln!("--preserve-paths requires true or false");
+                    std::process::exit(1);
+                }
+            },
             "--help" => {
                 print_help();
                 return Ok(());
             }
-            "--test" => {
-                run_test();
-                return Ok(());
-            }
             "-" => input_source = Some(InputSource::Stdin),
             _ if input_source.is_none() => input_source = Some(InputSource::File(arg.to_string())),
             _ => {
@@ -171,6 +87,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let config = builder.protect(protect_options).build();
+
+    if stream {
+        let stdout = io::stdout();
+        let writer = BufWriter::new(stdout.lock());
+        match input_source {
+            Some(InputSource::File(filename)) => {
+                lowher::transform_stream(&config, BufReader::new(fs::File::open(filename)?), writer)?
+            }
+            Some(InputSource::Stdin) | None => {
+                lowher::transform_stream(&config, io::stdin().lock(), writer)?
+            }
+        }
+        return Ok(());
+    }
+
     let content = match input_source {
         Some(InputSource::File(filename)) => fs::read_to_string(filename)?,
         Some(InputSource::Stdin) => {
@@ -185,7 +117,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    let output_text = lowher(&content, preserve_capitalized, preserve_sentence_case);
+    let output_text = lowher::transform(&config, &content);
     println!("{}", output_text);
 
     Ok(())
@@ -196,33 +128,12 @@ enum InputSource {
     Stdin,
 }
 
-fn run_test() {
-    let test_string = "This is a TEST String with ACRONYMS like NASA and proper Nouns like John Doe. \
-                       Here's some `inlineCode` and a code block:
-                       ```
-                       function testFunction() {
-                           console.log('HELLO WORLD');
-                       }
-                       ```
-                       More TEXT here. Let's include an email: John.Doe@Example.com and a URL: https://www.Example.com. \
-                       Another sentence. And one more.";
-
-    println!("Original text:");
-    println!("{}\n", test_string);
-
-    println!(
-        "Processed text (preserving capitalized words, lowercasing first letter of sentences):"
-    );
-    println!("{}\n", lowher(test_string, true, false));
-
-    println!("Processed text (lowercasing all words, lowercasing first letter of sentences):");
-    println!("{}\n", lowher(test_string, false, false));
-
-    println!("Processed text (preserving capitalized words and sentence case):");
-    println!("{}\n", lowher(test_string, true, true));
-
-    println!("Processed text (lowercasing all words, preserving sentence case):");
-    println!("{}", lowher(test_string, false, true));
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "on" | "1" => Some(true),
+        "false" | "off" | "0" => Some(false),
+        _ => None,
+    }
 }
 
 fn print_help() {
@@ -234,18 +145,49 @@ fn print_help() {
     println!(
         "  -s, --preserve-sentence-case  Preserve the case of the first letter in each sentence"
     );
+    println!("  -t, --title-case              Title-case each line, keeping small words lowercase");
+    println!("  --ignore <word>               Never change the case of <word> (repeatable; not applied with -t or --case)");
+    println!("  --ignore-file <path>          Load newline-separated words to never re-case");
+    println!(
+        "  --case <style>                Reshape identifiers into snake, kebab, camel, pascal, or title case"
+    );
+    println!(
+        "  --preserve-urls <bool>        Toggle protecting URLs from casing changes (default: true)"
+    );
+    println!(
+        "  --preserve-emails <bool>      Toggle protecting email addresses from casing changes (default: true)"
+    );
+    println!(
+        "  --preserve-paths <bool>       Toggle protecting filesystem paths from casing changes (default: true)"
+    );
+    println!(
+        "  --stream                      Process the input line-by-line instead of reading it all upfront; a fenced code block is held in memory until it closes"
+    );
+    println!(
+        "                                Note: chunk boundaries are fence boundaries, not sentence boundaries, so sentence"
+    );
+    println!(
+        "                                casing can differ from the non-streamed result when a sentence spans a line break"
+    );
     println!("  --help                        Print this help message");
     println!("  -                             Read from stdin instead of a file");
     println!("\nDescription:");
     println!(
         "  Lowher reads the content of the specified file or from stdin, converts it to lowercase"
     );
-    println!("  while optionally preserving the case of proper nouns and sentence beginnings, always preserving");
-    println!("  acronyms and code blocks. The result is printed to stdout.");
+    println!("  while optionally preserving the case of proper nouns and sentence beginnings. Code blocks,");
+    println!("  @mentions, and #hashtags are always preserved; URLs, emails, and filesystem paths are preserved");
+    println!("  unless turned off with their --preserve-* flag. The result is printed to stdout.");
     println!("\nExamples:");
     println!("  lowher input.txt > output.txt");
     println!("  lowher -a input.txt > output_all_lowercase.txt");
     println!("  lowher -s input.txt > output_preserve_sentence_case.txt");
+    println!("  lowher -t input.txt > output_title_case.txt");
+    println!("  lowher --ignore iPhone --ignore GitHub input.txt > output.txt");
+    println!("  lowher --ignore-file brands.txt input.txt > output.txt");
+    println!("  lowher --case snake identifiers.txt > snake_case_identifiers.txt");
+    println!("  lowher --preserve-paths false input.txt > output.txt");
+    println!("  cat huge.txt | lowher --stream - > output.txt");
     println!("  pbpaste | lowher - > output.txt");
     println!("  echo 'Some TEXT. Another sentence.' | lowher");
 }
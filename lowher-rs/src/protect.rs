@@ -0,0 +1,118 @@
+//! Protected-span registry: finds spans of text that casing transformations should never
+//! touch (code, URLs, emails, filesystem paths, @mentions, #hashtags), swaps each for a
+//! unique placeholder before processing, and restores the originals afterward.
+
+use regex::Regex;
+
+/// Which protected-span categories are active, beyond code blocks (which are always
+/// protected).
+#[derive(Debug, Clone, Copy)]
+pub struct ProtectOptions {
+    pub preserve_urls: bool,
+    pub preserve_emails: bool,
+    pub preserve_paths: bool,
+    pub preserve_mentions: bool,
+    pub preserve_hashtags: bool,
+}
+
+impl Default for ProtectOptions {
+    fn default() -> Self {
+        ProtectOptions {
+            preserve_urls: true,
+            preserve_emails: true,
+            preserve_paths: true,
+            preserve_mentions: true,
+            preserve_hashtags: true,
+        }
+    }
+}
+
+/// Ordered so that patterns matching a superset of another (e.g. emails containing `@`
+/// before @mentions) run first and consume their span before the narrower pattern sees it.
+fn span_patterns(options: &ProtectOptions) -> Vec<Regex> {
+    let mut patterns = vec![Regex::new(r"(```[\s\S]*?```|`[^`]*`)").unwrap()];
+
+    if options.preserve_urls {
+        patterns.push(Regex::new(r"https?://\S+").unwrap());
+    }
+    if options.preserve_emails {
+        patterns.push(Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+    }
+    if options.preserve_paths {
+        patterns.push(Regex::new(r"(?:~|\.{1,2})?(?:/[\w.-]+){2,}/?").unwrap());
+    }
+    if options.preserve_mentions {
+        patterns.push(Regex::new(r"@\w+").unwrap());
+    }
+    if options.preserve_hashtags {
+        patterns.push(Regex::new(r"#\w+").unwrap());
+    }
+
+    patterns
+}
+
+/// Picks a placeholder sentinel that is guaranteed not to already appear in `text`.
+///
+/// Built entirely from private-use-area code points (no ASCII letters, digits, or `_`),
+/// so it never matches `\w`-based patterns like the word/identifier regexes used by the
+/// other transforms; otherwise reshaping a placeholder's "word" would make it unrecognizable
+/// to [`unmark_protected_spans`] and the protected span would never be restored.
+fn make_sentinel(text: &str) -> String {
+    let mut sentinel = "\u{E000}\u{E001}".to_string();
+    while text.contains(&sentinel) {
+        sentinel.push('\u{E002}');
+    }
+    sentinel
+}
+
+/// Encodes `index` using private-use-area digit code points instead of ASCII digits,
+/// keeping the whole placeholder free of `\w` characters.
+fn encode_index(index: usize) -> String {
+    index
+        .to_string()
+        .chars()
+        .map(|digit| {
+            let value = digit.to_digit(10).expect("index is decimal");
+            char::from_u32(0xE010 + value).expect("0xE010..=0xE019 are valid code points")
+        })
+        .collect()
+}
+
+/// Replaces every protected span in `text` with a unique placeholder, in category order.
+/// Returns the marked text along with parallel placeholder/original-span vectors suitable
+/// for [`unmark_protected_spans`].
+pub fn mark_protected_spans(
+    text: &str,
+    options: &ProtectOptions,
+) -> (String, Vec<String>, Vec<String>) {
+    let sentinel = make_sentinel(text);
+    let mut marked_text = text.to_string();
+    let mut placeholders = Vec::new();
+    let mut spans = Vec::new();
+
+    for pattern in span_patterns(options) {
+        let matches: Vec<String> = pattern
+            .find_iter(&marked_text)
+            .map(|m| m.as_str().to_string())
+            .collect();
+
+        for span in matches {
+            let placeholder = format!("{sentinel}{}{sentinel}", encode_index(placeholders.len()));
+            marked_text = marked_text.replacen(&span, &placeholder, 1);
+            placeholders.push(placeholder);
+            spans.push(span);
+        }
+    }
+
+    (marked_text, placeholders, spans)
+}
+
+/// Restores the spans replaced by [`mark_protected_spans`], newest first, since a later
+/// span (e.g. a URL) may contain the placeholder of an earlier one (e.g. inline code).
+pub fn unmark_protected_spans(text: &str, placeholders: &[String], spans: &[String]) -> String {
+    let mut unmarked_text = text.to_string();
+    for (placeholder, span) in placeholders.iter().zip(spans.iter()).rev() {
+        unmarked_text = unmarked_text.replace(placeholder, span);
+    }
+    unmarked_text
+}